@@ -0,0 +1,23 @@
+//! Converters turn the native diagnostic output of third party tools into
+//! SARIF.
+
+use crate::sarif;
+use anyhow::Result;
+
+pub mod shellcheck;
+
+/// Combines `runs` produced by one or more converters (or read verbatim from
+/// an existing SARIF log) into a single SARIF document.
+///
+/// This is the entry point behind `--merge`: callers collect a
+/// [`sarif::Run`] per input and hand the whole list to `merge` once, rather
+/// than serializing each input on its own.
+pub fn merge(runs: Vec<sarif::Run>) -> Result<sarif::Sarif> {
+  Ok(
+    sarif::SarifBuilder::default()
+      .version(sarif::Version::V2_1_0.to_string())
+      .schema(sarif::SCHEMA_URL.to_string())
+      .runs(runs)
+      .build()?,
+  )
+}
@@ -0,0 +1,714 @@
+//! Converts shellcheck's `-f json` diagnostic output into SARIF.
+
+use crate::sarif;
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::io::{Read, Write};
+use std::str::FromStr;
+
+/// Key under which the shellcheck fingerprint is stored in
+/// `result.partialFingerprints`. Versioned so a future change to the
+/// hashing scheme doesn't collide with fingerprints already recorded by
+/// GHAS or another SARIF consumer.
+const FINGERPRINT_KEY: &str = "shellcheckHash/v1";
+
+/// Base URL for shellcheck's per-code wiki pages, e.g.
+/// `https://www.shellcheck.net/wiki/SC2086`.
+const WIKI_BASE_URL: &str = "https://www.shellcheck.net/wiki";
+
+/// shellcheck's four severities, ordered from least to most severe so a
+/// `--level` threshold can be compared with a simple `>=`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+  Style,
+  Info,
+  Warning,
+  Error,
+}
+
+impl FromStr for Level {
+  type Err = anyhow::Error;
+
+  fn from_str(level: &str) -> Result<Self> {
+    match level {
+      "error" => Ok(Level::Error),
+      "warning" => Ok(Level::Warning),
+      "info" => Ok(Level::Info),
+      "style" => Ok(Level::Style),
+      other => Err(anyhow!("unknown shellcheck level: {other}")),
+    }
+  }
+}
+
+impl Level {
+  /// The SARIF `result.level` this shellcheck severity maps onto.
+  ///
+  /// shellcheck's `info` and `style` both collapse onto SARIF's `note`,
+  /// since SARIF has no equivalent finer-grained distinction.
+  fn sarif_level(self) -> &'static str {
+    match self {
+      Level::Error => "error",
+      Level::Warning => "warning",
+      Level::Info | Level::Style => "note",
+    }
+  }
+
+  /// A 0-100 `result.rank`, giving consumers a deterministic numeric
+  /// ordering on top of the coarser `level`.
+  fn rank(self) -> f64 {
+    match self {
+      Level::Error => 100.0,
+      Level::Warning => 70.0,
+      Level::Info => 40.0,
+      Level::Style => 10.0,
+    }
+  }
+
+  /// A GHAS-style `security-severity` (0.0-10.0) result property.
+  fn security_severity(self) -> f64 {
+    match self {
+      Level::Error => 9.0,
+      Level::Warning => 5.0,
+      Level::Info => 2.0,
+      Level::Style => 0.0,
+    }
+  }
+}
+
+/// Drops comments whose severity is below `threshold`, e.g. with
+/// `threshold = Level::Warning` only `error` and `warning` comments survive.
+pub fn filter_by_level(
+  comments: Vec<ShellcheckComment>,
+  threshold: Level,
+) -> Result<Vec<ShellcheckComment>> {
+  comments
+    .into_iter()
+    .filter_map(|comment| match Level::from_str(&comment.level) {
+      Ok(level) if level >= threshold => Some(Ok(comment)),
+      Ok(_) => None,
+      Err(err) => Some(Err(err)),
+    })
+    .collect()
+}
+
+/// A single `replacements[]` entry from a shellcheck comment's `fix` object.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ShellcheckReplacement {
+  pub line: i64,
+  pub end_line: i64,
+  pub column: i64,
+  pub end_column: i64,
+  pub precedence: i64,
+  pub insertion_point: String,
+  pub replacement: String,
+}
+
+/// shellcheck's autofix suggestion for a comment.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ShellcheckFix {
+  pub replacements: Vec<ShellcheckReplacement>,
+}
+
+/// A single entry of shellcheck's `-f json` output.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ShellcheckComment {
+  pub file: String,
+  pub line: i64,
+  pub end_line: i64,
+  pub column: i64,
+  pub end_column: i64,
+  pub level: String,
+  pub code: i64,
+  pub message: String,
+  #[serde(default)]
+  pub fix: Option<ShellcheckFix>,
+}
+
+/// Builds a SARIF `result.fixes[]` entry from a shellcheck `fix` object.
+///
+/// All of a comment's replacements are grouped into a single `fix`, ordered
+/// by `precedence` so that overlapping edits are applied deterministically.
+fn parse_fix(file: &str, fix: &ShellcheckFix) -> Result<sarif::Fix> {
+  let mut replacements = fix.replacements.clone();
+  replacements.sort_by_key(|r| r.precedence);
+
+  let artifact_replacements = replacements
+    .iter()
+    .map(|replacement| {
+      Ok(
+        sarif::ReplacementBuilder::default()
+          .deleted_region(
+            sarif::RegionBuilder::default()
+              .start_line(replacement.line)
+              .start_column(replacement.column)
+              .end_line(replacement.end_line)
+              .end_column(replacement.end_column)
+              .build()?,
+          )
+          .inserted_content(
+            sarif::ArtifactContentBuilder::default()
+              .text(replacement.replacement.clone())
+              .build()?,
+          )
+          .build()?,
+      )
+    })
+    .collect::<Result<Vec<_>>>()?;
+
+  let artifact_change = sarif::ArtifactChangeBuilder::default()
+    .artifact_location(
+      sarif::ArtifactLocationBuilder::default()
+        .uri(file)
+        .build()?,
+    )
+    .replacements(artifact_replacements)
+    .build()?;
+
+  Ok(
+    sarif::FixBuilder::default()
+      .artifact_changes(vec![artifact_change])
+      .build()?,
+  )
+}
+
+/// Reads and trims every line of `file` once, so per-comment fingerprinting
+/// doesn't re-read and re-split the same (potentially large) file for every
+/// finding it contains.
+fn read_lines(file: &str) -> Option<Vec<String>> {
+  let contents = std::fs::read_to_string(file).ok()?;
+  Some(contents.lines().map(str::trim).map(str::to_string).collect())
+}
+
+/// Looks up the trimmed text of `line` (1-indexed) in an already-read
+/// `lines` cache, or `None` if the file couldn't be read or doesn't have
+/// that many lines.
+fn line_text<'a>(lines: Option<&'a Vec<String>>, line: i64) -> Option<&'a str> {
+  let index = usize::try_from(line - 1).ok()?;
+  lines?.get(index).map(String::as_str)
+}
+
+/// The stable part of a fingerprint's hash input for a single comment.
+///
+/// Normally this is the offending line's trimmed text, which (unlike the
+/// line number) survives unrelated edits elsewhere in the file. When
+/// `file` couldn't be read back (stdin input, a path that doesn't resolve
+/// from the tool's cwd, permissions, ...), `lines` is `None`; falling back
+/// to an empty string there would silently collapse every finding in that
+/// file onto the same fingerprint. Fall back to the line number instead,
+/// and warn, so findings stay distinguishable even though the fingerprint
+/// is no longer stable across line shifts for that file.
+fn fingerprint_context(file: &str, line: i64, lines: Option<&Vec<String>>) -> String {
+  match line_text(lines, line) {
+    Some(text) => text.to_string(),
+    None => {
+      eprintln!(
+        "warning: shellcheck-sarif: could not read '{file}' to build a stable \
+         fingerprint for line {line}; falling back to the line number, so this \
+         fingerprint will change if surrounding lines shift"
+      );
+      format!("line:{line}")
+    }
+  }
+}
+
+/// Computes the `shellcheckHash/v1` fingerprint for a result.
+///
+/// The hash covers the normalized file path, the rule code and the
+/// offending line's trimmed text, deliberately excluding line/column
+/// numbers so the fingerprint survives unrelated edits elsewhere in the
+/// file. `column` is mixed in only to disambiguate two results that would
+/// otherwise collide (same file, same line, same code).
+fn fingerprint(file: &str, code: i64, context: &str, column: Option<i64>) -> String {
+  let normalized_file = file.replace('\\', "/");
+
+  let mut hasher = Sha256::new();
+  hasher.update(normalized_file.as_bytes());
+  hasher.update([0u8]);
+  hasher.update(code.to_string().as_bytes());
+  hasher.update([0u8]);
+  hasher.update(context.as_bytes());
+  if let Some(column) = column {
+    hasher.update([0u8]);
+    hasher.update(column.to_string().as_bytes());
+  }
+
+  hasher
+    .finalize()
+    .iter()
+    .take(16)
+    .map(|byte| format!("{byte:02x}"))
+    .collect()
+}
+
+/// Builds the `reportingDescriptor` for a shellcheck code, linking to its
+/// wiki page so SARIF viewers can surface clickable documentation.
+fn parse_rule(code: i64) -> Result<sarif::ReportingDescriptor> {
+  let id = format!("SC{code}");
+  let help_uri = format!("{WIKI_BASE_URL}/{id}");
+
+  Ok(
+    sarif::ReportingDescriptorBuilder::default()
+      .id(id)
+      .help_uri(help_uri)
+      .build()?,
+  )
+}
+
+fn parse_result(
+  comment: &ShellcheckComment,
+  rule_indices: &BTreeMap<i64, usize>,
+  disambiguate_by_column: bool,
+  file_lines: &BTreeMap<&str, Option<Vec<String>>>,
+) -> Result<sarif::Result> {
+  let level = Level::from_str(&comment.level)?;
+  let lines = file_lines
+    .get(comment.file.as_str())
+    .and_then(|lines| lines.as_ref());
+  let context = fingerprint_context(&comment.file, comment.line, lines);
+  let column = disambiguate_by_column.then_some(comment.column);
+  let mut builder = sarif::ResultBuilder::default();
+
+  builder
+    .rule_id(format!("SC{}", comment.code))
+    .rule_index(*rule_indices.get(&comment.code).expect(
+      "every comment's code was registered when the rule set was built",
+    ) as i64)
+    .message(
+      sarif::MessageBuilder::default()
+        .text(comment.message.clone())
+        .build()?,
+    )
+    .level(level.sarif_level().to_string())
+    .rank(level.rank())
+    .partial_fingerprints(BTreeMap::from([(
+      FINGERPRINT_KEY.to_string(),
+      fingerprint(&comment.file, comment.code, &context, column),
+    )]))
+    .properties(
+      sarif::PropertyBagBuilder::default()
+        .additional_properties(BTreeMap::from([(
+          "security-severity".to_string(),
+          serde_json::Value::from(format!("{:.1}", level.security_severity())),
+        )]))
+        .build()?,
+    )
+    .locations(vec![sarif::LocationBuilder::default()
+      .physical_location(
+        sarif::PhysicalLocationBuilder::default()
+          .artifact_location(
+            sarif::ArtifactLocationBuilder::default()
+              .uri(comment.file.clone())
+              .build()?,
+          )
+          .region(
+            sarif::RegionBuilder::default()
+              .start_line(comment.line)
+              .start_column(comment.column)
+              .end_line(comment.end_line)
+              .end_column(comment.end_column)
+              .build()?,
+          )
+          .build()?,
+      )
+      .build()?]);
+
+  if let Some(fix) = &comment.fix {
+    builder.fixes(vec![parse_fix(&comment.file, fix)?]);
+  }
+
+  Ok(builder.build()?)
+}
+
+/// Converts a list of shellcheck comments into a single SARIF [`sarif::Run`].
+///
+/// This is the entry point used when a shellcheck run needs to be merged
+/// with runs coming from other tools, e.g. [`super::merge`].
+pub fn parse_to_run(comments: &[ShellcheckComment]) -> Result<sarif::Run> {
+  // Assign each distinct code a stable index, in first-seen order, so
+  // `result.ruleIndex` can point back into `driver.rules[]`.
+  let mut rule_indices = BTreeMap::new();
+  let mut codes = Vec::new();
+  for comment in comments {
+    if !rule_indices.contains_key(&comment.code) {
+      rule_indices.insert(comment.code, codes.len());
+      codes.push(comment.code);
+    }
+  }
+
+  let rules = codes
+    .iter()
+    .map(|code| parse_rule(*code))
+    .collect::<Result<Vec<_>>>()?;
+
+  // Two results sharing a (file, line, code) would otherwise hash to an
+  // identical fingerprint; disambiguate those by also mixing in the column.
+  let mut occurrences_by_site: BTreeMap<(&str, i64, i64), usize> = BTreeMap::new();
+  for comment in comments {
+    *occurrences_by_site
+      .entry((comment.file.as_str(), comment.line, comment.code))
+      .or_default() += 1;
+  }
+
+  // Read each distinct file once, however many findings it has, rather than
+  // re-reading and re-splitting it per comment.
+  let mut file_lines: BTreeMap<&str, Option<Vec<String>>> = BTreeMap::new();
+  for comment in comments {
+    file_lines
+      .entry(comment.file.as_str())
+      .or_insert_with(|| read_lines(&comment.file));
+  }
+
+  let results = comments
+    .iter()
+    .map(|comment| {
+      let disambiguate_by_column = occurrences_by_site
+        [&(comment.file.as_str(), comment.line, comment.code)]
+        > 1;
+      parse_result(comment, &rule_indices, disambiguate_by_column, &file_lines)
+    })
+    .collect::<Result<Vec<_>>>()?;
+
+  Ok(
+    sarif::RunBuilder::default()
+      .tool(
+        sarif::ToolBuilder::default()
+          .driver(
+            sarif::ToolComponentBuilder::default()
+              .name("shellcheck")
+              .rules(rules)
+              .build()?,
+          )
+          .build()?,
+      )
+      .results(results)
+      .build()?,
+  )
+}
+
+/// A key identifying "the same finding" across two SARIF runs.
+///
+/// Prefers the `shellcheckHash/v1` fingerprint; falls back to
+/// `(ruleId, uri, region)` for results that don't have one (e.g. hand
+/// written or from a tool version that predates fingerprinting).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum ResultKey {
+  Fingerprint(String),
+  Location(
+    Option<String>,
+    Option<String>,
+    Option<i64>,
+    Option<i64>,
+    Option<i64>,
+    Option<i64>,
+  ),
+}
+
+fn result_key(result: &sarif::Result) -> ResultKey {
+  if let Some(hash) = result
+    .partial_fingerprints
+    .as_ref()
+    .and_then(|fingerprints| fingerprints.get(FINGERPRINT_KEY))
+  {
+    return ResultKey::Fingerprint(hash.clone());
+  }
+
+  let physical_location = result
+    .locations
+    .as_ref()
+    .and_then(|locations| locations.first())
+    .and_then(|location| location.physical_location.as_ref());
+  let uri = physical_location
+    .and_then(|physical| physical.artifact_location.as_ref())
+    .and_then(|artifact| artifact.uri.clone());
+  let region = physical_location.and_then(|physical| physical.region.as_ref());
+
+  ResultKey::Location(
+    result.rule_id.clone(),
+    uri,
+    region.and_then(|region| region.start_line),
+    region.and_then(|region| region.start_column),
+    region.and_then(|region| region.end_line),
+    region.and_then(|region| region.end_column),
+  )
+}
+
+/// Annotates every result in `run` with a `baselineState` of `"new"` or
+/// `"unchanged"` relative to `baseline_results`, and appends an `"absent"`
+/// result for every baseline finding that no longer appears in `run`.
+pub fn diff_against_baseline(
+  mut run: sarif::Run,
+  baseline_results: &[sarif::Result],
+) -> Result<sarif::Run> {
+  let mut unmatched_baseline: std::collections::HashMap<ResultKey, sarif::Result> =
+    baseline_results
+      .iter()
+      .map(|result| (result_key(result), result.clone()))
+      .collect();
+
+  for result in &mut run.results {
+    // A baseline result without a fingerprint is keyed by its location, so
+    // also try the current result's location key before calling it new.
+    let mut without_fingerprint = result.clone();
+    without_fingerprint.partial_fingerprints = None;
+    let matched = unmatched_baseline.remove(&result_key(result)).is_some()
+      || unmatched_baseline
+        .remove(&result_key(&without_fingerprint))
+        .is_some();
+    result.baseline_state = Some(
+      if matched {
+        "unchanged".to_string()
+      } else {
+        "new".to_string()
+      },
+    );
+  }
+
+  for mut absent in unmatched_baseline.into_values() {
+    absent.baseline_state = Some("absent".to_string());
+    run.results.push(absent);
+  }
+
+  Ok(run)
+}
+
+/// Converts a list of shellcheck comments into a SARIF [`sarif::Sarif`].
+pub fn parse(comments: &[ShellcheckComment]) -> Result<sarif::Sarif> {
+  super::merge(vec![parse_to_run(comments)?])
+}
+
+/// Parses shellcheck's `-f json` output read from `reader` into a list of
+/// [`ShellcheckComment`]s.
+pub fn from_reader<R>(mut reader: R) -> Result<Vec<ShellcheckComment>>
+where
+  R: Read,
+{
+  let mut input = String::new();
+  reader.read_to_string(&mut input)?;
+  Ok(serde_json::from_str(&input)?)
+}
+
+/// Reads shellcheck's `-f json` output from `reader`, converts it to SARIF,
+/// and writes the result to `writer`.
+pub fn parse_to_writer<R, W>(reader: R, writer: W) -> Result<()>
+where
+  R: Read,
+  W: Write,
+{
+  let comments = from_reader(reader)?;
+  let sarif = parse(&comments)?;
+
+  serde_json::to_writer_pretty(writer, &sarif)?;
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn comment(file: &str, line: i64, column: i64, code: i64) -> ShellcheckComment {
+    ShellcheckComment {
+      file: file.to_string(),
+      line,
+      end_line: line,
+      column,
+      end_column: column + 1,
+      level: "warning".to_string(),
+      code,
+      message: format!("SC{code} at {file}:{line}"),
+      fix: None,
+    }
+  }
+
+  #[test]
+  fn parse_fix_orders_replacements_by_precedence() {
+    let comment = ShellcheckComment {
+      fix: Some(ShellcheckFix {
+        replacements: vec![
+          ShellcheckReplacement {
+            line: 1,
+            end_line: 1,
+            column: 10,
+            end_column: 12,
+            precedence: 2,
+            insertion_point: "afterEnd".to_string(),
+            replacement: "second".to_string(),
+          },
+          ShellcheckReplacement {
+            line: 1,
+            end_line: 1,
+            column: 1,
+            end_column: 3,
+            precedence: 1,
+            insertion_point: "afterEnd".to_string(),
+            replacement: "first".to_string(),
+          },
+        ],
+      }),
+      ..comment("script.sh", 1, 1, 2086)
+    };
+
+    let fix = parse_fix("script.sh", comment.fix.as_ref().unwrap()).unwrap();
+    let replacements = &fix.artifact_changes[0].replacements.as_ref().unwrap();
+
+    assert_eq!(
+      replacements[0].inserted_content.as_ref().unwrap().text,
+      Some("first".to_string())
+    );
+    assert_eq!(
+      replacements[1].inserted_content.as_ref().unwrap().text,
+      Some("second".to_string())
+    );
+  }
+
+  #[test]
+  fn parse_to_run_dedups_rules_and_wires_rule_index() {
+    let comments = vec![
+      comment("a.sh", 1, 1, 2086),
+      comment("b.sh", 2, 1, 2148),
+      comment("c.sh", 3, 1, 2086),
+    ];
+
+    let run = parse_to_run(&comments).unwrap();
+    let rules = run.tool.driver.rules.as_ref().unwrap();
+
+    assert_eq!(rules.len(), 2, "SC2086 must be deduped across comments");
+    assert_eq!(rules[0].id, "SC2086");
+    assert_eq!(
+      rules[0].help_uri,
+      Some("https://www.shellcheck.net/wiki/SC2086".to_string())
+    );
+    assert_eq!(rules[1].id, "SC2148");
+
+    assert_eq!(run.results[0].rule_index, Some(0));
+    assert_eq!(run.results[1].rule_index, Some(1));
+    assert_eq!(
+      run.results[2].rule_index,
+      Some(0),
+      "the second SC2086 comment must point back at the same rule entry as the first"
+    );
+  }
+
+  #[test]
+  fn merge_concatenates_runs_in_order() {
+    let run_a = parse_to_run(&[comment("a.sh", 1, 1, 2086)]).unwrap();
+    let run_b = parse_to_run(&[comment("b.sh", 2, 1, 2148)]).unwrap();
+
+    let sarif = super::super::merge(vec![run_a.clone(), run_b.clone()]).unwrap();
+
+    assert_eq!(sarif.runs.len(), 2);
+    assert_eq!(sarif.runs[0].results[0].rule_id, run_a.results[0].rule_id);
+    assert_eq!(sarif.runs[1].results[0].rule_id, run_b.results[0].rule_id);
+  }
+
+  fn with_level(mut comment: ShellcheckComment, level: &str) -> ShellcheckComment {
+    comment.level = level.to_string();
+    comment
+  }
+
+  #[test]
+  fn filter_by_level_drops_everything_below_the_threshold() {
+    let comments = vec![
+      with_level(comment("a.sh", 1, 1, 2086), "error"),
+      with_level(comment("a.sh", 2, 1, 2086), "warning"),
+      with_level(comment("a.sh", 3, 1, 2086), "info"),
+      with_level(comment("a.sh", 4, 1, 2086), "style"),
+    ];
+
+    let filtered = filter_by_level(comments, Level::Warning).unwrap();
+
+    assert_eq!(filtered.len(), 2);
+    assert_eq!(filtered[0].level, "error");
+    assert_eq!(filtered[1].level, "warning");
+  }
+
+  #[test]
+  fn level_maps_onto_the_documented_sarif_levels() {
+    assert_eq!(Level::Error.sarif_level(), "error");
+    assert_eq!(Level::Warning.sarif_level(), "warning");
+    assert_eq!(Level::Info.sarif_level(), "note");
+    assert_eq!(Level::Style.sarif_level(), "note");
+  }
+
+  #[test]
+  fn fingerprint_is_stable_across_line_shifts() {
+    let path = std::env::temp_dir().join("shellcheck_sarif_test_line_shift.sh");
+
+    std::fs::write(&path, "#!/bin/sh\necho $foo\n").unwrap();
+    let lines_before = read_lines(path.to_str().unwrap());
+    let context_before = fingerprint_context(path.to_str().unwrap(), 2, lines_before.as_ref());
+    let fingerprint_before = fingerprint(path.to_str().unwrap(), 2086, &context_before, None);
+
+    // Two unrelated lines are inserted above the flagged line, pushing it
+    // from line 2 down to line 4.
+    std::fs::write(&path, "#!/bin/sh\n\n\necho $foo\n").unwrap();
+    let lines_after = read_lines(path.to_str().unwrap());
+    let context_after = fingerprint_context(path.to_str().unwrap(), 4, lines_after.as_ref());
+    let fingerprint_after = fingerprint(path.to_str().unwrap(), 2086, &context_after, None);
+
+    std::fs::remove_file(&path).ok();
+
+    assert_eq!(
+      fingerprint_before, fingerprint_after,
+      "the fingerprint must not change when only the flagged line's position shifts"
+    );
+  }
+
+  #[test]
+  fn fingerprint_disambiguates_identical_same_line_findings_by_column() {
+    let fingerprint_a = fingerprint("a.sh", 2086, "echo $foo $bar", Some(6));
+    let fingerprint_b = fingerprint("a.sh", 2086, "echo $foo $bar", Some(11));
+
+    assert_ne!(fingerprint_a, fingerprint_b);
+  }
+
+  #[test]
+  fn parse_to_run_disambiguates_duplicate_same_line_findings() {
+    let comments = vec![comment("a.sh", 1, 6, 2086), comment("a.sh", 1, 11, 2086)];
+
+    let run = parse_to_run(&comments).unwrap();
+    let fingerprint_of = |result: &sarif::Result| {
+      result
+        .partial_fingerprints
+        .as_ref()
+        .unwrap()
+        .get(FINGERPRINT_KEY)
+        .unwrap()
+        .clone()
+    };
+
+    assert_ne!(
+      fingerprint_of(&run.results[0]),
+      fingerprint_of(&run.results[1]),
+      "two distinct comments on the same file/line/code must not collide"
+    );
+  }
+
+  #[test]
+  fn diff_against_baseline_marks_fixed_and_new_findings() {
+    // `a.sh`'s finding was fixed (it's only in the baseline); `b.sh`'s is
+    // newly introduced (it's only in the current run).
+    let baseline_run = parse_to_run(&[comment("a.sh", 1, 1, 2086)]).unwrap();
+    let current_run = parse_to_run(&[comment("b.sh", 1, 1, 2148)]).unwrap();
+
+    let diffed = diff_against_baseline(current_run, &baseline_run.results).unwrap();
+
+    let absent_count = diffed
+      .results
+      .iter()
+      .filter(|result| result.baseline_state.as_deref() == Some("absent"))
+      .count();
+    assert_eq!(absent_count, 1);
+
+    let new_count = diffed
+      .results
+      .iter()
+      .filter(|result| result.baseline_state.as_deref() == Some("new"))
+      .count();
+    assert_eq!(new_count, 1);
+  }
+}
@@ -0,0 +1,12 @@
+#![doc(html_root_url = "https://docs.rs/serde_sarif")]
+
+//! This crate provides (de)serializable data structures for the SARIF
+//! (Static Analysis Results Interchange Format) schema, along with a set of
+//! `converters` that turn the native output of various static analysis
+//! tools into SARIF.
+//!
+//! The `sarif` module is generated directly from the official SARIF 2.1.0
+//! JSON schema and should not be hand edited.
+
+pub mod converters;
+pub mod sarif;
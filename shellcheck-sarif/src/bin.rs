@@ -74,9 +74,52 @@
 
 use anyhow::Result;
 use clap::{App, Arg};
+use serde_sarif::converters::shellcheck::{self, Level};
+use serde_sarif::{converters, sarif};
 use std::fs::File;
 use std::io::{BufReader, BufWriter, Read, Write};
 use std::path::Path;
+use std::str::FromStr;
+
+/// Opens `path` for reading, or stdin if `path` is `None`.
+fn reader_for(path: Option<&Path>) -> Result<Box<dyn Read>> {
+  Ok(match path {
+    Some(path) => Box::new(File::open(path)?) as Box<dyn Read>,
+    None => Box::new(std::io::stdin()) as Box<dyn Read>,
+  })
+}
+
+/// Reads shellcheck comments from `path` (or stdin), dropping anything below
+/// `level` when one was requested.
+fn comments_for(
+  path: Option<&Path>,
+  level: Option<Level>,
+) -> Result<Vec<shellcheck::ShellcheckComment>> {
+  let comments = shellcheck::from_reader(BufReader::new(reader_for(path)?))?;
+  match level {
+    Some(level) => shellcheck::filter_by_level(comments, level),
+    None => Ok(comments),
+  }
+}
+
+/// Reads an already-SARIF log from `path` and returns its runs unchanged, so
+/// they can be concatenated with freshly converted ones.
+fn runs_from_sarif_file(path: &Path) -> Result<Vec<sarif::Run>> {
+  let reader = BufReader::new(File::open(path)?);
+  let sarif: sarif::Sarif = serde_json::from_reader(reader)?;
+  Ok(sarif.runs)
+}
+
+/// Reads every result out of every run of the SARIF log at `path`, for use
+/// as the baseline in `--baseline` mode.
+fn results_from_sarif_file(path: &Path) -> Result<Vec<sarif::Result>> {
+  Ok(
+    runs_from_sarif_file(path)?
+      .into_iter()
+      .flat_map(|run| run.results)
+      .collect(),
+  )
+}
 
 fn main() -> Result<()> {
   let matches = App::new("shellcheck-sarif")
@@ -87,9 +130,30 @@ fn main() -> Result<()> {
     .version(env!("CARGO_PKG_VERSION"))
     .arg(
       Arg::new("input")
-        .help("input file; reads from stdin if none is given")
+        .help("input file(s); reads from stdin if none is given")
+        .takes_value(true)
+        .multiple_values(true),
+    )
+    .arg(
+      Arg::new("level")
+        .help("drop comments below this severity before emitting SARIF")
+        .long("level")
+        .takes_value(true)
+        .possible_values(["error", "warning", "info", "style"]),
+    )
+    .arg(
+      Arg::new("baseline")
+        .help("prior SARIF log; annotates results with baselineState (new/unchanged/absent)")
+        .long("baseline")
         .takes_value(true),
     )
+    .arg(
+      Arg::new("merge")
+        .help("existing SARIF file whose runs are merged in unchanged; may be given multiple times")
+        .long("merge")
+        .takes_value(true)
+        .multiple_occurrences(true),
+    )
     .arg(
       Arg::new("output")
         .help("output file; writes to stdout if none is given")
@@ -99,17 +163,62 @@ fn main() -> Result<()> {
     )
     .get_matches();
 
-  let read = match matches.value_of_os("input").map(Path::new) {
-    Some(path) => Box::new(File::open(path)?) as Box<dyn Read>,
-    None => Box::new(std::io::stdin()) as Box<dyn Read>,
+  let inputs: Vec<&Path> = matches
+    .values_of_os("input")
+    .map(|values| values.map(Path::new).collect())
+    .unwrap_or_default();
+
+  let level = matches
+    .value_of("level")
+    .map(Level::from_str)
+    .transpose()?;
+
+  // Every positional input is shellcheck's own output, so all of their
+  // comments are collapsed into a single `ShellcheckComment` batch and
+  // converted together into one run. This keeps rule dedup (chunk0-2) and
+  // same-line disambiguation (chunk0-5) working across the whole
+  // invocation rather than per input file.
+  let comments: Vec<shellcheck::ShellcheckComment> = if inputs.is_empty() {
+    comments_for(None, level)?
+  } else {
+    inputs
+      .iter()
+      .map(|path| comments_for(Some(path), level))
+      .collect::<Result<Vec<_>>>()?
+      .into_iter()
+      .flatten()
+      .collect()
   };
-  let reader = BufReader::new(read);
+
+  let mut shellcheck_run = converters::shellcheck::parse_to_run(&comments)?;
+
+  if let Some(baseline_path) = matches.value_of("baseline").map(Path::new) {
+    let baseline_results = results_from_sarif_file(baseline_path)?;
+    shellcheck_run = shellcheck::diff_against_baseline(shellcheck_run, &baseline_results)?;
+  }
+
+  let merged_runs: Vec<sarif::Run> = matches
+    .values_of_os("merge")
+    .into_iter()
+    .flatten()
+    .map(|path| runs_from_sarif_file(Path::new(path)))
+    .collect::<Result<Vec<_>>>()?
+    .into_iter()
+    .flatten()
+    .collect();
+
+  let runs = std::iter::once(shellcheck_run)
+    .chain(merged_runs)
+    .collect();
+  let sarif = converters::merge(runs)?;
 
   let write = match matches.value_of_os("output").map(Path::new) {
     Some(path) => Box::new(File::create(path)?) as Box<dyn Write>,
     None => Box::new(std::io::stdout()) as Box<dyn Write>,
   };
-  let writer = BufWriter::new(write);
+  let mut writer = BufWriter::new(write);
 
-  serde_sarif::converters::shellcheck::parse_to_writer(reader, writer)
+  serde_json::to_writer_pretty(&mut writer, &sarif)?;
+  writer.flush()?;
+  Ok(())
 }